@@ -4,11 +4,21 @@ use orbitcurve::OrbitCurve;
 use debug::ComputeDebugInfo;
 use uuid::Uuid;
 use std::f64;
+use std::collections::HashMap;
 
 #[derive(Clone, Debug)]
 pub struct Drawables {
     pub orbit_bodies: Vec<OrbitBody>,
     pub orbit_curves: Vec<OrbitCurve>,
+    curve_cache: HashMap<Uuid, CachedCurve>,
+}
+
+/// A body's sampled curve plots, cached alongside the zoom level they were
+/// generated at so unchanged zooms skip resampling the whole trajectory.
+#[derive(Clone, Debug)]
+struct CachedCurve {
+    zoom: f32,
+    plots: Vec<Vector2<f32>>,
 }
 
 impl Drawables {
@@ -47,6 +57,7 @@ impl Drawables {
         Drawables {
             orbit_bodies: bodies,
             orbit_curves: Vec::new(),
+            curve_cache: HashMap::new(),
         }
     }
 
@@ -66,6 +77,181 @@ impl Drawables {
         }
         false
     }
+
+    /// Bodies & curves intersecting `view_rect`, so rendering & picking can
+    /// skip off-screen geometry at high zoom-out. Curves are culled by
+    /// segment/rect overlap, bodies by circle/rect overlap.
+    pub fn visible<'a>(&'a self, view_rect: &'a Rect) -> impl Iterator<Item = Drawable<'a>> + 'a {
+        let bodies = self.orbit_bodies.iter()
+            .filter(move |body| circle_intersects_rect(body.center, body.radius as f32, view_rect))
+            .map(Drawable::Body);
+
+        let curves = self.orbit_curves.iter()
+            .filter(move |curve| curve.plots.windows(2)
+                .any(|segment| segment_intersects_rect(segment[0], segment[1], view_rect)))
+            .map(Drawable::Curve);
+
+        bodies.chain(curves)
+    }
+
+    /// Resamples `orbit_curves` with adaptive, curvature-based subdivision:
+    /// `position_at` integrates a body's orbit forward to absolute time `t`, and
+    /// points are spent where the trajectory bends rather than at a fixed step.
+    /// Each body's curve is cached by `Uuid` alongside the `zoom` it was sampled
+    /// at, and only resampled once `zoom` has moved by more than
+    /// `CURVE_ZOOM_RESAMPLE_THRESHOLD`, or when `curve_body_mismatch` reports
+    /// any curve as stale, in which case every cached curve is resampled.
+    pub fn resample_curves<F>(&mut self, zoom: f32, fault_tolerance: f64, mut position_at: F)
+        where F: FnMut(Uuid, f64) -> Vector2<f32>
+    {
+        let stale = self.curve_body_mismatch(fault_tolerance);
+        let tolerance = zoom * CURVE_TOLERANCE_FRACTION;
+
+        for body in &self.orbit_bodies {
+            let needs_resample = match self.curve_cache.get(&body.id) {
+                Some(cached) => stale || (cached.zoom - zoom).abs() > zoom * CURVE_ZOOM_RESAMPLE_THRESHOLD,
+                None => true,
+            };
+            if !needs_resample {
+                continue;
+            }
+
+            let plots = adaptive_sample(|t| position_at(body.id, t), tolerance);
+            self.curve_cache.insert(body.id, CachedCurve { zoom, plots });
+        }
+
+        self.orbit_curves = self.orbit_bodies.iter()
+            .map(|body| OrbitCurve { plots: self.curve_cache[&body.id].plots.clone() })
+            .collect();
+    }
+}
+
+/// World-space curve sampling tolerance, as a fraction of zoom (so it stays a
+/// roughly constant number of screen pixels regardless of zoom level).
+const CURVE_TOLERANCE_FRACTION: f32 = 0.002;
+
+/// Fractional zoom change (relative to the current zoom) before
+/// that curve is considered stale and resampled.
+const CURVE_ZOOM_RESAMPLE_THRESHOLD: f32 = 0.1;
+
+/// Smallest time step `adaptive_sample` will subdivide down to.
+const CURVE_MIN_STEP: f64 = 1e-4;
+
+/// Upper bound on points per curve, guarding against runaway subdivision.
+const CURVE_MAX_POINTS: usize = 4096;
+
+/// Integrates a curve forward via `position_at(t)`, halving the step whenever
+/// the midpoint `p1` deviates from the straight chord `p0`->`p2` by more than
+/// `tolerance`, and doubling it when well under tolerance.
+fn adaptive_sample<F>(mut position_at: F, tolerance: f32) -> Vec<Vector2<f32>>
+    where F: FnMut(f64) -> Vector2<f32>
+{
+    let mut plots = Vec::new();
+    let mut t = 0f64;
+    let mut step = 1f64;
+    let mut p0 = position_at(t);
+    let mut p1 = position_at(t + step);
+    plots.push(p0);
+
+    while plots.len() < CURVE_MAX_POINTS && step > CURVE_MIN_STEP {
+        let p2 = position_at(t + step * 2.0);
+        let deviation = point_segment_distance(p1, p0, p2);
+
+        if deviation > tolerance && step * 0.5 > CURVE_MIN_STEP {
+            step *= 0.5;
+            p1 = position_at(t + step);
+            continue;
+        }
+
+        plots.push(p1);
+        t += step;
+        p0 = p1;
+        p1 = p2;
+
+        if deviation < tolerance * 0.25 {
+            step *= 2.0;
+            p1 = position_at(t + step);
+        }
+    }
+
+    plots
+}
+
+/// A single culled drawable returned by `Drawables::visible`.
+pub enum Drawable<'a> {
+    Body(&'a OrbitBody),
+    Curve(&'a OrbitCurve),
+}
+
+/// Axis-aligned bounding box in world space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rect {
+    pub min: Vector2<f32>,
+    pub max: Vector2<f32>,
+}
+
+impl Rect {
+    pub fn width(&self) -> f32 {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> f32 {
+        self.max.y - self.min.y
+    }
+
+    pub fn contains(&self, point: Vector2<f32>) -> bool {
+        point.x >= self.min.x && point.x <= self.max.x &&
+        point.y >= self.min.y && point.y <= self.max.y
+    }
+
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.min.x <= other.max.x && self.max.x >= other.min.x &&
+        self.min.y <= other.max.y && self.max.y >= other.min.y
+    }
+
+    /// Returns this rect grown outward by `margin` on every side.
+    pub fn expand(&self, margin: f32) -> Rect {
+        Rect {
+            min: Vector2::new(self.min.x - margin, self.min.y - margin),
+            max: Vector2::new(self.max.x + margin, self.max.y + margin),
+        }
+    }
+}
+
+/// True if a circle at `center` with `radius` overlaps `rect`.
+fn circle_intersects_rect(center: Vector2<f32>, radius: f32, rect: &Rect) -> bool {
+    let closest = Vector2::new(
+        center.x.max(rect.min.x).min(rect.max.x),
+        center.y.max(rect.min.y).min(rect.max.y),
+    );
+    center.distance(closest) <= radius
+}
+
+/// True if the segment `a`->`b` overlaps `rect`, via Liang-Barsky clipping.
+fn segment_intersects_rect(a: Vector2<f32>, b: Vector2<f32>, rect: &Rect) -> bool {
+    let d = b - a;
+    let mut t_min = 0f32;
+    let mut t_max = 1f32;
+
+    let clip = |p: f32, q: f32, t_min: &mut f32, t_max: &mut f32| -> bool {
+        if p == 0.0 {
+            return q >= 0.0;
+        }
+        let t = q / p;
+        if p < 0.0 {
+            if t > *t_max { return false; }
+            if t > *t_min { *t_min = t; }
+        } else {
+            if t < *t_min { return false; }
+            if t < *t_max { *t_max = t; }
+        }
+        true
+    };
+
+    clip(-d.x, a.x - rect.min.x, &mut t_min, &mut t_max) &&
+    clip(d.x, rect.max.x - a.x, &mut t_min, &mut t_max) &&
+    clip(-d.y, a.y - rect.min.y, &mut t_min, &mut t_max) &&
+    clip(d.y, rect.max.y - a.y, &mut t_min, &mut t_max)
 }
 
 fn birds_eye_at_z(height: f32) -> Matrix4<f32> {
@@ -74,13 +260,103 @@ fn birds_eye_at_z(height: f32) -> Matrix4<f32> {
     view
 }
 
+/// How quickly the camera eases toward its target each frame; larger eases faster.
+const CAMERA_EASE_RATE: f32 = 12.0;
+
+/// Current & target camera values, eased together over time so pans and zooms
+/// feel fluid rather than snapping straight to their destination.
 #[derive(Clone, Debug)]
-pub struct State {
+pub struct Camera {
     pub origin: Vector2<f32>,
     pub zoom: f32,
+    pub view: Matrix4<f32>,
+    target_origin: Vector2<f32>,
+    target_zoom: f32,
+    was_updated: bool,
+}
+
+impl Camera {
+    fn new() -> Camera {
+        let zoom = 16f32;
+        Camera {
+            origin: Vector2::new(0.0f32, 0.0),
+            zoom,
+            view: birds_eye_at_z(1.0),
+            target_origin: Vector2::new(0.0f32, 0.0),
+            target_zoom: zoom,
+            was_updated: true,
+        }
+    }
+
+    /// Eases `origin` & `zoom` toward their targets by `dt` seconds worth of
+    /// exponential smoothing, setting `was_updated` if either moved.
+    pub fn update(&mut self, dt: f32) {
+        let ease = 1.0 - (-CAMERA_EASE_RATE * dt).exp();
+        let origin = self.origin + (self.target_origin - self.origin) * ease;
+        let zoom = self.zoom + (self.target_zoom - self.zoom) * ease;
+
+        if origin != self.origin || zoom != self.zoom {
+            self.was_updated = true;
+        }
+        self.origin = origin;
+        self.zoom = zoom;
+    }
+}
+
+/// Distance the perspective eye is pulled back along +z per unit of zoom.
+const PERSPECTIVE_ZOOM_DISTANCE: f32 = 2.0;
+
+/// Far clip plane distance used by the perspective projection.
+const PERSPECTIVE_FAR_PLANE_DISTANCE: f32 = 10_000.0;
+
+/// Selects between the existing flat top-down orthographic view and a
+/// perspective camera that can tilt and fly over the n-body system.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ProjectionMode {
+    Orthographic,
+    Perspective {
+        vertical_fov: Rad<f32>,
+        near_plane_distance: f32,
+        /// Tilt away from a straight top-down view: `Rad(0.0)` looks straight
+        /// down (matching orthographic mode), increasing toward `Rad(PI/2)`
+        /// for a horizon-level, flying-over view.
+        pitch: Rad<f32>,
+    },
+}
+
+/// Builds a perspective view matrix for an eye pulled back along +z
+/// proportional to `zoom` and tilted by `pitch` away from straight down,
+/// looking at `origin` on the z=0 plane.
+fn birds_eye_perspective_at(origin: Vector2<f32>, zoom: f32, pitch: Rad<f32>) -> Matrix4<f32> {
+    let distance = zoom * PERSPECTIVE_ZOOM_DISTANCE;
+    let sin_pitch = pitch.sin();
+    let cos_pitch = pitch.cos();
+
+    let eye = Point3::new(origin.x, origin.y - sin_pitch * distance, cos_pitch * distance);
+    let target = Point3::new(origin.x, origin.y, 0.0);
+    let up = Vector3::new(0.0, cos_pitch, sin_pitch);
+    Matrix4::look_at(eye, target, up)
+}
+
+/// Packed camera/screen uniforms uploaded to the GPU once per frame they change.
+#[derive(Clone, Copy, Debug)]
+pub struct Globals {
+    pub projection: [[f32; 4]; 4],
+    pub origin: [f32; 2],
+    pub zoom: f32,
+    pub screen_size: [f32; 2],
+}
+
+#[derive(Clone, Debug)]
+pub struct State {
+    pub camera: Camera,
     pub screen_width: u32,
     pub screen_height: u32,
-    pub view: Matrix4<f32>,
+    /// HiDPI scale factor (physical pixels per logical pixel) reported by the
+    /// windowing backend; mouse positions arrive in logical pixels while
+    /// `screen_width`/`screen_height` are physical, so this reconciles the two.
+    pub scale_factor: f32,
+    pub projection_mode: ProjectionMode,
     pub user_quit: bool,
     pub drawables: Drawables,
     pub debug_info: ComputeDebugInfo,
@@ -91,11 +367,11 @@ pub struct State {
 impl State {
     pub fn new(screen_width: u32, screen_height: u32) -> State {
         State {
-            origin: Vector2::new(0.0f32, 0.0),
-            zoom: 16f32,
+            camera: Camera::new(),
             screen_width,
             screen_height,
-            view: birds_eye_at_z(1.0),
+            scale_factor: 1.0,
+            projection_mode: ProjectionMode::Orthographic,
             user_quit: false,
             drawables: Drawables::initial(),
             debug_info: ComputeDebugInfo::initial(),
@@ -105,12 +381,29 @@ impl State {
     }
 
     pub fn projection(&self) -> Matrix4<f32> {
-        ortho(self.origin.x - self.zoom * self.aspect_ratio(),
-              self.origin.x + self.zoom * self.aspect_ratio(),
-              self.origin.y - self.zoom,
-              self.origin.y + self.zoom,
-              1.0,
-              -1.0)
+        match self.projection_mode {
+            ProjectionMode::Orthographic => ortho(
+                self.camera.origin.x - self.camera.zoom * self.aspect_ratio(),
+                self.camera.origin.x + self.camera.zoom * self.aspect_ratio(),
+                self.camera.origin.y - self.camera.zoom,
+                self.camera.origin.y + self.camera.zoom,
+                1.0,
+                -1.0),
+            ProjectionMode::Perspective { vertical_fov, near_plane_distance, .. } => perspective(
+                vertical_fov,
+                self.aspect_ratio(),
+                near_plane_distance,
+                PERSPECTIVE_FAR_PLANE_DISTANCE),
+        }
+    }
+
+    /// The view matrix for the current `projection_mode`.
+    pub fn view(&self) -> Matrix4<f32> {
+        match self.projection_mode {
+            ProjectionMode::Orthographic => self.camera.view,
+            ProjectionMode::Perspective { pitch, .. } =>
+                birds_eye_perspective_at(self.camera.origin, self.camera.zoom, pitch),
+        }
     }
 
     pub fn aspect_ratio(&self) -> f32 {
@@ -118,24 +411,162 @@ impl State {
     }
 
     /// translates screen pixels into world co-ordinates in the orthographic projection
+    ///
+    /// `pixels` is expected in logical coordinates, as reported by the windowing
+    /// backend, so is multiplied by `scale_factor` before mapping onto the
+    /// physical `screen_width`/`screen_height` framebuffer.
     pub fn screen_to_world_normalised<V: Into<Vector2<i32>>>(&self, pixels: V) -> Vector2<f32> {
         let pixels = pixels.into();
-        let x_world = self.zoom * self.aspect_ratio() * (pixels.x as f32 * 2.0 / self.screen_width as f32 - 1f32);
-        let y_world = self.zoom * (-pixels.y as f32 * 2.0 / self.screen_height as f32 + 1f32);
+        let x = pixels.x as f32 * self.scale_factor;
+        let y = pixels.y as f32 * self.scale_factor;
+        let x_world = self.camera.zoom * self.aspect_ratio() * (x * 2.0 / self.screen_width as f32 - 1f32);
+        let y_world = self.camera.zoom * (-y * 2.0 / self.screen_height as f32 + 1f32);
         Vector2::new(x_world, y_world)
     }
 
+    /// Converts screen pixels into world co-ordinates: in `Orthographic` mode
+    /// this is exact, in `Perspective` mode it unprojects the pixel onto the
+    /// z=0 simulation plane by intersecting the eye ray with that plane.
     pub fn screen_to_world<V: Into<Vector2<i32>>>(&self, pixels: V) -> Vector2<f32> {
-        self.origin + self.screen_to_world_normalised(pixels)
+        match self.projection_mode {
+            ProjectionMode::Orthographic => self.camera.origin + self.screen_to_world_normalised(pixels),
+            ProjectionMode::Perspective { vertical_fov, pitch, .. } =>
+                self.screen_to_world_perspective(pixels.into(), vertical_fov, pitch),
+        }
+    }
+
+    /// Unprojects `pixels` through the tilted perspective camera by
+    /// intersecting the eye ray with the z=0 simulation plane.
+    fn screen_to_world_perspective(&self, pixels: Vector2<i32>, vertical_fov: Rad<f32>, pitch: Rad<f32>) -> Vector2<f32> {
+        let x = pixels.x as f32 * self.scale_factor;
+        let y = pixels.y as f32 * self.scale_factor;
+        let ndc_x = x * 2.0 / self.screen_width as f32 - 1f32;
+        let ndc_y = -y * 2.0 / self.screen_height as f32 + 1f32;
+
+        let tan_half_fov = (vertical_fov / 2.0).tan();
+        let sin_pitch = pitch.sin();
+        let cos_pitch = pitch.cos();
+
+        // Camera basis matching `birds_eye_perspective_at`'s eye/up/target.
+        let right = Vector3::new(1.0, 0.0, 0.0);
+        let up = Vector3::new(0.0, cos_pitch, sin_pitch);
+        let forward = Vector3::new(0.0, sin_pitch, -cos_pitch);
+
+        let ray_dir = forward
+            + right * (ndc_x * tan_half_fov * self.aspect_ratio())
+            + up * (ndc_y * tan_half_fov);
+
+        let distance = self.camera.zoom * PERSPECTIVE_ZOOM_DISTANCE;
+        let eye = Vector3::new(self.camera.origin.x, self.camera.origin.y - sin_pitch * distance, cos_pitch * distance);
+
+        let t = -eye.z / ray_dir.z;
+        let hit = eye + ray_dir * t;
+        Vector2::new(hit.x, hit.y)
+    }
+
+    /// Zooms the camera's target by `factor` (< 1 zooms in, > 1 zooms out) while
+    /// keeping the world point currently under `pixels` fixed on screen.
+    pub fn zoom_towards(&mut self, pixels: Vector2<i32>, factor: f32) {
+        let world_before = self.screen_to_world_normalised(pixels);
+        let new_zoom = (self.camera.target_zoom * factor).max(f32::EPSILON);
+        let world_after = world_before * (new_zoom / self.camera.zoom);
+
+        self.camera.target_zoom = new_zoom;
+        self.camera.target_origin = self.camera.origin + world_before - world_after;
+        self.camera.was_updated = true;
+    }
+
+    /// Pans the camera's target by `pixel_delta`, screen-space pixels converted
+    /// to world units at the current zoom level.
+    pub fn pan_by(&mut self, pixel_delta: Vector2<i32>) {
+        let delta = self.screen_to_world_normalised(pixel_delta) - self.screen_to_world_normalised(Vector2::new(0, 0));
+        self.camera.target_origin -= delta;
+        self.camera.was_updated = true;
+    }
+
+    /// Packs the current camera & screen state for GPU upload, or `None` if
+    /// the camera has not been marked `was_updated` since the last call.
+    /// Changes to the screen size or `projection_mode` alone do not mark it.
+    pub fn globals(&mut self) -> Option<Globals> {
+        if !self.camera.was_updated {
+            return None;
+        }
+        self.camera.was_updated = false;
+        Some(Globals {
+            projection: self.projection().into(),
+            origin: self.camera.origin.into(),
+            zoom: self.camera.zoom,
+            screen_size: [self.screen_width as f32, self.screen_height as f32],
+        })
+    }
+
+    /// Returns the axis-aligned world rect currently visible on screen:
+    /// - min: bottom left, least x & y visible world location
+    /// - max: top right, most x & y visible world location
+    pub fn visible_world_range(&self) -> Rect {
+        Rect {
+            min: self.screen_to_world(Vector2::new(0, self.screen_height as i32)),
+            max: self.screen_to_world(Vector2::new(self.screen_width as i32, 0)),
+        }
+    }
+
+    /// Hit-tests `pixels` against the drawables, returning whichever body or
+    /// curve is closest, provided it's within a zoom-scaled pixel tolerance (so
+    /// the tolerance stays constant in screen space regardless of zoom level).
+    pub fn pick(&self, pixels: Vector2<i32>) -> Option<PickResult> {
+        let world_pt = self.screen_to_world(pixels);
+        let tolerance = self.camera.zoom * 2.0 * PICK_TOLERANCE_PX / self.screen_height as f32;
+
+        let mut best: Option<(f32, PickResult)> = None;
+
+        for body in &self.drawables.orbit_bodies {
+            let dist = (world_pt.distance(body.center) - body.radius as f32).max(0.0);
+            if dist <= tolerance && best.as_ref().map_or(true, |&(best_dist, _)| dist < best_dist) {
+                best = Some((dist, PickResult::Body(body.id)));
+            }
+        }
+
+        for (idx, curve) in self.drawables.orbit_curves.iter().enumerate() {
+            if let Some(dist) = curve_distance(curve, world_pt) {
+                if dist <= tolerance && best.as_ref().map_or(true, |&(best_dist, _)| dist < best_dist) {
+                    best = Some((dist, PickResult::Curve(self.drawables.orbit_bodies[idx].id)));
+                }
+            }
+        }
+
+        best.map(|(_, result)| result)
     }
+}
+
+/// Pixel tolerance used when picking bodies/curves under the cursor.
+const PICK_TOLERANCE_PX: f32 = 6.0;
 
-    /// Returns tuple with (min, max) coord corners
-    /// - left: bottom left, least x & y visible world location
-    /// - right: top right, most x & y visible world location
-    pub fn visible_world_range(&self) -> (Vector2<f32>, Vector2<f32>) {
-        (self.screen_to_world(Vector2::new(0, self.screen_height as i32)),
-         self.screen_to_world(Vector2::new(self.screen_width as i32, 0)))
+/// The result of a `State::pick` hit-test.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PickResult {
+    Body(Uuid),
+    Curve(Uuid),
+}
+
+/// Minimum distance from `p` to the line segment `a`->`b`, falling back to
+/// `|p - a|` when the segment is degenerate (`a == b`).
+fn point_segment_distance(p: Vector2<f32>, a: Vector2<f32>, b: Vector2<f32>) -> f32 {
+    let ab = b - a;
+    let len_sq = ab.dot(ab);
+    if len_sq == 0.0 {
+        return p.distance(a);
     }
+    let t = (p - a).dot(ab) / len_sq;
+    let t = t.max(0.0).min(1.0);
+    p.distance(a + ab * t)
+}
+
+/// Minimum distance from `p` to `curve`'s polyline, or `None` if the curve has
+/// fewer than two plots.
+fn curve_distance(curve: &OrbitCurve, p: Vector2<f32>) -> Option<f32> {
+    curve.plots.windows(2)
+        .map(|segment| point_segment_distance(p, segment[0], segment[1]))
+        .fold(None, |closest, dist| Some(closest.map_or(dist, |c: f32| c.min(dist))))
 }
 
 #[cfg(test)]
@@ -157,7 +588,7 @@ mod state_test {
     // :z zoom
     fn test_screen_to_world(s: State) {
         let a = s.aspect_ratio();
-        let z = s.zoom;
+        let z = s.camera.zoom;
         assert_eq!(s.screen_to_world(Vector2::new(0, 0)),
             Vector2::new(-a * z, 1f32 * z), "top-left");
         assert_eq!(s.screen_to_world(Vector2::new(s.screen_width as i32, 0)),
@@ -200,14 +631,299 @@ mod state_test {
         //     └─┘
         //        (1a,-1)
         let mut state = State::new(160, 90);
-        state.zoom = 0.33f32;
+        state.camera.zoom = 0.33f32;
         test_screen_to_world(state);
     }
 
+    #[test]
+    fn screen_to_world_scale_factor() {
+        // a 1.5x HiDPI display: screen_width/height are physical pixels, but
+        // pixels passed to screen_to_world arrive in logical pixels, i.e.
+        // physical = logical * scale_factor.
+        let mut state = State::new(150, 90);
+        state.scale_factor = 1.5;
+        state.camera.zoom = 10f32;
+        let a = state.aspect_ratio();
+        let z = state.camera.zoom;
+
+        assert_eq!(state.screen_to_world(Vector2::new(0, 0)),
+            Vector2::new(-a * z, 1f32 * z), "top-left (logical origin)");
+        assert_eq!(state.screen_to_world(Vector2::new(100, 60)),
+            Vector2::new(a * z, -1f32 * z), "bottom-right: logical (100,60) == physical (150,90)");
+        assert_eq!(state.screen_to_world(Vector2::new(50, 30)),
+            Vector2::new(0f32, 0f32), "center: logical (50,30) == physical (75,45)");
+    }
+
     #[test]
     fn visible_world_range() {
         let mut state = State::new(180, 90);
-        state.zoom = 3f32;
-        assert_eq!(state.visible_world_range(), ((-6_f32, -3_f32).into(), (6_f32, 3_f32).into()));
+        state.camera.zoom = 3f32;
+        assert_eq!(state.visible_world_range(), Rect { min: (-6_f32, -3_f32).into(), max: (6_f32, 3_f32).into() });
+    }
+
+    #[test]
+    fn camera_update_converges_towards_target() {
+        let mut camera = Camera::new();
+        camera.was_updated = false;
+        camera.target_origin = Vector2::new(10.0, 0.0);
+        camera.target_zoom = 32.0;
+
+        camera.update(0.1);
+
+        assert!(camera.was_updated, "origin/zoom moved, so was_updated should be set");
+        assert!(camera.origin.x > 0.0 && camera.origin.x < camera.target_origin.x,
+            "origin should have eased partway toward target, got {:?}", camera.origin);
+        assert!(camera.zoom > 16.0 && camera.zoom < camera.target_zoom,
+            "zoom should have eased partway toward target, got {}", camera.zoom);
+
+        // enough further updates should converge arbitrarily close to the target
+        for _ in 0..200 {
+            camera.update(0.1);
+        }
+        assert!((camera.origin.x - 10.0).abs() < 1e-3);
+        assert!((camera.zoom - 32.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn camera_update_is_noop_once_converged() {
+        let mut camera = Camera::new();
+        camera.was_updated = false;
+        camera.update(0.1);
+        assert!(!camera.was_updated, "origin/zoom already at target, nothing should change");
+    }
+
+    #[test]
+    fn zoom_towards_keeps_cursor_world_point_fixed() {
+        let mut state = State::new(200, 200);
+        state.camera.zoom = 16.0;
+        state.camera.target_zoom = 16.0;
+
+        let pixel = Vector2::new(150, 50);
+        let world_before = state.screen_to_world(pixel);
+
+        state.zoom_towards(pixel, 0.5);
+        // run the eased update to completion so the camera reaches its targets
+        for _ in 0..200 {
+            state.camera.update(0.1);
+        }
+
+        let world_after = state.screen_to_world(pixel);
+        assert!((world_before.x - world_after.x).abs() < 1e-2,
+            "world point under cursor should stay fixed on screen: before={:?} after={:?}", world_before, world_after);
+        assert!((world_before.y - world_after.y).abs() < 1e-2);
+        assert_eq!(state.camera.target_zoom, 8.0, "factor 0.5 should halve the target zoom");
+    }
+
+    #[test]
+    fn pan_by_moves_target_origin_by_world_delta() {
+        let mut state = State::new(200, 200);
+        state.camera.zoom = 16.0;
+        let before_target = state.camera.target_origin;
+
+        state.pan_by(Vector2::new(20, 0));
+
+        // panning right on screen moves the target origin left in world space
+        assert!(state.camera.target_origin.x < before_target.x);
+        assert_eq!(state.camera.target_origin.y, before_target.y);
+        assert!(state.camera.was_updated);
+    }
+
+    #[test]
+    fn globals_returns_none_once_consumed_then_some_again_after_change() {
+        let mut state = State::new(100, 100);
+        // a freshly constructed State's camera already starts was_updated=true
+        assert!(state.globals().is_some(), "first call after construction should yield Some");
+        assert!(state.globals().is_none(), "nothing changed since last call, should be None");
+
+        state.pan_by(Vector2::new(5, 0));
+        assert!(state.globals().is_some(), "pan_by should mark the camera dirty");
+        assert!(state.globals().is_none(), "consuming globals() again with no further change should be None");
+    }
+
+    #[test]
+    fn point_segment_distance_basics() {
+        let a = Vector2::new(0.0, 0.0);
+        let b = Vector2::new(10.0, 0.0);
+        assert_eq!(point_segment_distance(Vector2::new(5.0, 3.0), a, b), 3.0, "perpendicular to middle of segment");
+        assert_eq!(point_segment_distance(Vector2::new(-2.0, 0.0), a, b), 2.0, "beyond the `a` end");
+        assert_eq!(point_segment_distance(Vector2::new(12.0, 0.0), a, b), 2.0, "beyond the `b` end");
+
+        let p = Vector2::new(5.0, 1.0);
+        assert_eq!(point_segment_distance(p, a, a), p.distance(a), "degenerate a == b segment");
+    }
+
+    #[test]
+    fn curve_distance_basics() {
+        let curve = OrbitCurve {
+            plots: vec!(Vector2::new(0.0, 0.0), Vector2::new(10.0, 0.0), Vector2::new(10.0, 10.0)),
+        };
+        assert_eq!(curve_distance(&curve, Vector2::new(5.0, 2.0)), Some(2.0), "nearest the first segment");
+        assert_eq!(curve_distance(&curve, Vector2::new(12.0, 5.0)), Some(2.0), "nearest the second segment");
+
+        let single_plot = OrbitCurve { plots: vec!(Vector2::new(0.0, 0.0)) };
+        assert_eq!(curve_distance(&single_plot, Vector2::new(1.0, 1.0)), None, "fewer than 2 plots");
+    }
+
+    #[test]
+    fn pick_returns_closest_body_within_tolerance() {
+        let state = State::new(200, 200);
+        let body0 = state.drawables.orbit_bodies[0].id;
+        // body 0 is centered on the world origin, which the screen center maps to
+        assert_eq!(state.pick(Vector2::new(100, 100)), Some(PickResult::Body(body0)));
+    }
+
+    #[test]
+    fn pick_returns_none_outside_tolerance() {
+        let state = State::new(200, 200);
+        // the top-left corner is far from every body & curve
+        assert_eq!(state.pick(Vector2::new(0, 0)), None);
+    }
+
+    #[test]
+    fn pick_returns_closest_curve_within_tolerance() {
+        let mut state = State::new(200, 200);
+        // the screen center maps to camera.origin, so place it away from every
+        // body but on top of a curve, to isolate the curve-hit branch
+        state.camera.origin = Vector2::new(3.5, 5.0);
+        state.camera.target_origin = state.camera.origin;
+
+        let target_body = state.drawables.orbit_bodies[1].id;
+        state.drawables.orbit_curves = state.drawables.orbit_bodies.iter().enumerate()
+            .map(|(idx, _)| if idx == 1 {
+                OrbitCurve { plots: vec!(Vector2::new(3.4, 5.0), Vector2::new(3.6, 5.0)) }
+            } else {
+                OrbitCurve { plots: Vec::new() }
+            })
+            .collect();
+
+        assert_eq!(state.pick(Vector2::new(100, 100)), Some(PickResult::Curve(target_body)),
+            "curve hit should resolve to the body at the same index as the curve");
+    }
+
+    #[test]
+    fn rect_basics() {
+        let r = Rect { min: Vector2::new(-1.0, -2.0), max: Vector2::new(3.0, 4.0) };
+        assert_eq!(r.width(), 4.0);
+        assert_eq!(r.height(), 6.0);
+        assert!(r.contains(Vector2::new(0.0, 0.0)));
+        assert!(!r.contains(Vector2::new(10.0, 0.0)));
+
+        let overlapping = Rect { min: Vector2::new(2.0, 2.0), max: Vector2::new(5.0, 5.0) };
+        assert!(r.intersects(&overlapping));
+        let far = Rect { min: Vector2::new(10.0, 10.0), max: Vector2::new(20.0, 20.0) };
+        assert!(!r.intersects(&far));
+
+        assert_eq!(r.expand(1.0), Rect { min: Vector2::new(-2.0, -3.0), max: Vector2::new(4.0, 5.0) });
+    }
+
+    #[test]
+    fn circle_intersects_rect_basics() {
+        let r = Rect { min: Vector2::new(0.0, 0.0), max: Vector2::new(10.0, 10.0) };
+        assert!(circle_intersects_rect(Vector2::new(5.0, 5.0), 1.0, &r), "circle inside rect");
+        assert!(circle_intersects_rect(Vector2::new(-0.5, 5.0), 1.0, &r), "circle overlapping left edge");
+        assert!(!circle_intersects_rect(Vector2::new(-5.0, 5.0), 1.0, &r), "circle far outside rect");
+    }
+
+    #[test]
+    fn segment_intersects_rect_basics() {
+        let r = Rect { min: Vector2::new(0.0, 0.0), max: Vector2::new(10.0, 10.0) };
+        assert!(segment_intersects_rect(Vector2::new(-5.0, 5.0), Vector2::new(5.0, 5.0), &r), "segment crossing into rect");
+        assert!(!segment_intersects_rect(Vector2::new(-5.0, 20.0), Vector2::new(-1.0, 20.0), &r), "segment entirely outside rect");
+    }
+
+    #[test]
+    fn drawables_visible_culls_outside_view() {
+        let mut drawables = Drawables::initial();
+        drawables.orbit_curves = drawables.orbit_bodies.iter()
+            .map(|b| OrbitCurve { plots: vec!(b.center, b.center + Vector2::new(1.0, 0.0)) })
+            .collect();
+
+        // only body 0, centered on the world origin, and its curve fall within this view
+        let view = Rect { min: Vector2::new(-1.0, -1.0), max: Vector2::new(1.0, 1.0) };
+        let visible: Vec<_> = drawables.visible(&view).collect();
+
+        let bodies = visible.iter().filter(|d| if let Drawable::Body(_) = d { true } else { false }).count();
+        let curves = visible.iter().filter(|d| if let Drawable::Curve(_) = d { true } else { false }).count();
+        assert_eq!(bodies, 1);
+        assert_eq!(curves, 1);
+    }
+
+    #[test]
+    fn adaptive_sample_starts_at_t_zero() {
+        let plots = adaptive_sample(|t| Vector2::new(t as f32, 0.0), 0.01);
+        assert_eq!(plots[0], Vector2::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn adaptive_sample_does_not_collapse_at_tiny_tolerance() {
+        // a very small tolerance (as seen at high zoom) used to make every
+        // halving fire until step <= CURVE_MIN_STEP, at which point the loop
+        // exited without ever pushing the in-flight point, collapsing the
+        // curve down to its single starting plot instead of subdividing it.
+        let plots = adaptive_sample(|t| Vector2::new(t.sin() as f32, t.cos() as f32), 1e-9);
+        assert!(plots.len() > 1, "curve should keep subdividing, not collapse to a single point");
+    }
+
+    #[test]
+    fn resample_curves_populates_one_curve_per_body() {
+        let mut drawables = Drawables::initial();
+        let origin = drawables.orbit_bodies[0].center;
+
+        drawables.resample_curves(16.0, 0.01, |_, t| origin + Vector2::new(t as f32, 0.0));
+
+        assert_eq!(drawables.orbit_curves.len(), drawables.orbit_bodies.len());
+        for curve in &drawables.orbit_curves {
+            assert_eq!(curve.plots[0], origin);
+        }
+    }
+
+    #[test]
+    fn projection_perspective_builds_without_panicking() {
+        let mut state = State::new(160, 90);
+        state.projection_mode = ProjectionMode::Perspective {
+            vertical_fov: Rad(1.0),
+            near_plane_distance: 0.1,
+            pitch: Rad(0.2),
+        };
+        state.projection();
+    }
+
+    #[test]
+    fn screen_to_world_perspective_matches_ortho_when_pitch_is_zero() {
+        // at pitch=0 the perspective camera still looks straight down, so the
+        // screen center should unproject onto the camera's origin, same as ortho
+        let mut state = State::new(160, 90);
+        state.camera.zoom = 10.0;
+        state.projection_mode = ProjectionMode::Perspective {
+            vertical_fov: Rad(1.0),
+            near_plane_distance: 0.1,
+            pitch: Rad(0.0),
+        };
+
+        let center = state.screen_to_world(Vector2::new(80, 45));
+        assert!(center.x.abs() < 1e-3 && center.y.abs() < 1e-3,
+            "center pixel should unproject onto the camera origin, got {:?}", center);
+    }
+
+    #[test]
+    fn screen_to_world_perspective_tilts_with_pitch() {
+        let mut flat = State::new(160, 90);
+        flat.camera.zoom = 10.0;
+        flat.projection_mode = ProjectionMode::Perspective {
+            vertical_fov: Rad(1.0), near_plane_distance: 0.1, pitch: Rad(0.0),
+        };
+
+        let mut tilted = flat.clone();
+        tilted.projection_mode = ProjectionMode::Perspective {
+            vertical_fov: Rad(1.0), near_plane_distance: 0.1, pitch: Rad(0.3),
+        };
+
+        // an off-center pixel, below screen middle
+        let pixel = Vector2::new(80, 70);
+        let flat_hit = flat.screen_to_world(pixel);
+        let tilted_hit = tilted.screen_to_world(pixel);
+
+        assert!((flat_hit.y - tilted_hit.y).abs() > 1e-3,
+            "pitch should change where an off-center pixel unprojects: flat={:?} tilted={:?}", flat_hit, tilted_hit);
     }
 }